@@ -1,4 +1,5 @@
 pub mod catl;
+pub mod clip_history;
 pub mod get_file_path;
 pub mod get_github_file_link;
 pub mod install_dev_tools;
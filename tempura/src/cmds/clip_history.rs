@@ -0,0 +1,31 @@
+use std::fmt::Debug;
+
+use anyhow::anyhow;
+use anyhow::Context;
+
+pub fn run<'a>(mut args: impl Iterator<Item = &'a str> + Debug) -> anyhow::Result<()> {
+    let history = crate::utils::clipboard_history::load()?;
+
+    let Some(entry_idx) = args
+        .next()
+        .map(|idx| {
+            idx.parse::<usize>()
+                .with_context(|| format!("invalid idx {idx:?}"))
+        })
+        .transpose()?
+    else {
+        for (idx, entry) in history.iter().enumerate().rev() {
+            println!("{idx}\t{}", entry.escape_debug());
+        }
+        return Ok(());
+    };
+
+    let entry = history.get(entry_idx).ok_or_else(|| {
+        anyhow!(
+            "no clipboard history entry with idx {entry_idx}, history has {} entries",
+            history.len()
+        )
+    })?;
+
+    crate::utils::clipboard::copy_to_system_clipboard(&mut entry.as_bytes())
+}
@@ -25,7 +25,7 @@ pub fn run<'a>(_args: impl Iterator<Item = &'a str>) -> anyhow::Result<()> {
 
     let hx_status_line = HxStatusLine::from_str(hx_status_line)?;
 
-    crate::utils::system::copy_to_system_clipboard(
+    crate::utils::clipboard::copy_to_system_clipboard(
         &mut format_hx_status_line(&hx_status_line)?.as_bytes(),
     )?;
 
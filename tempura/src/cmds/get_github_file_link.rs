@@ -1,4 +1,3 @@
-use std::path::Component;
 use std::path::Path;
 use std::path::PathBuf;
 use std::process::Command;
@@ -10,6 +9,7 @@ use anyhow::bail;
 use url::Url;
 
 use crate::cmds::open_editor::Editor;
+use crate::utils::git::HeadState;
 use crate::utils::hx::HxCursorPosition;
 use crate::utils::hx::HxStatusLine;
 use crate::utils::wezterm::get_current_pane_sibling_matching_titles;
@@ -36,15 +36,8 @@ pub fn run<'a>(_args: impl Iterator<Item = &'a str>) -> anyhow::Result<()> {
     let git_repo_root = Arc::new(get_git_repo_root(&hx_status_line.file_path)?);
 
     let git_repo_root_clone = git_repo_root.clone();
-    let get_git_current_branch = std::thread::spawn(move || -> anyhow::Result<String> {
-        Ok(String::from_utf8(
-            Command::new("git")
-                .args(["-C", &git_repo_root_clone, "branch", "--show-current"])
-                .output()?
-                .stdout,
-        )?
-        .trim()
-        .to_owned())
+    let get_git_head_state = std::thread::spawn(move || -> anyhow::Result<HeadState> {
+        crate::utils::git::get_head_state(&git_repo_root_clone)
     });
 
     let git_repo_root_clone = git_repo_root.clone();
@@ -64,12 +57,12 @@ pub fn run<'a>(_args: impl Iterator<Item = &'a str>) -> anyhow::Result<()> {
 
     let github_link = build_github_link(
         &crate::utils::system::join(get_github_repo_url)?,
-        &crate::utils::system::join(get_git_current_branch)?,
+        crate::utils::system::join(get_git_head_state)?.tree_ref()?,
         hx_cursor_absolute_file_path.strip_prefix(git_repo_root.as_ref())?,
         &hx_status_line.position,
     )?;
 
-    crate::utils::system::copy_to_system_clipboard(&mut github_link.as_str().as_bytes())?;
+    crate::utils::clipboard::copy_to_system_clipboard(&mut github_link.as_str().as_bytes())?;
 
     Ok(())
 }
@@ -134,24 +127,17 @@ fn build_hx_cursor_absolute_file_path(
     hx_pane: &WezTermPane,
 ) -> anyhow::Result<PathBuf> {
     if let Ok(hx_cursor_file_path) = hx_cursor_file_path.strip_prefix("~") {
-        let mut home_absolute_path = Path::new(&std::env::var("HOME")?).to_path_buf();
+        let mut home_absolute_path = crate::utils::dirs::home_dir()?;
         home_absolute_path.push(hx_cursor_file_path);
         return Ok(home_absolute_path);
     }
 
-    let mut components = hx_pane.cwd.components();
-    components.next();
-    components.next();
-
-    Ok(std::iter::once(Component::RootDir)
-        .chain(components)
-        .chain(hx_cursor_file_path.components())
-        .collect())
+    Ok(hx_pane.absolute_cwd().join(hx_cursor_file_path))
 }
 
 fn build_github_link<'a>(
     github_repo_url: &'a Url,
-    git_current_branch: &'a str,
+    git_tree_ref: &'a str,
     file_path: &'a Path,
     hx_cursor_position: &'a HxCursorPosition,
 ) -> anyhow::Result<Url> {
@@ -165,7 +151,7 @@ fn build_github_link<'a>(
         );
     }
 
-    let segments = [&["tree", git_current_branch], file_path_parts.as_slice()].concat();
+    let segments = [&["tree", git_tree_ref], file_path_parts.as_slice()].concat();
     let mut github_link = github_repo_url.clone();
     github_link
         .path_segments_mut()
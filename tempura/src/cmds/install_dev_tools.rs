@@ -1,4 +1,5 @@
 use std::fmt::Debug;
+use std::path::Path;
 
 use anyhow::anyhow;
 
@@ -175,8 +176,12 @@ pub fn run<'a>(mut args: impl Iterator<Item = &'a str> + Debug) -> anyhow::Resul
         installers
             .iter()
             .fold(vec![], |mut acc, installer| {
-                let running_installer = scope
-                    .spawn(move || tools::report_install(installer.bin(), installer.install()));
+                let running_installer = scope.spawn(move || {
+                    tools::report_install(
+                        installer.bin(),
+                        tools::install_with_smoke_test(installer.as_ref(), bin_dir),
+                    )
+                });
                 acc.push((installer.bin(), running_installer));
                 acc
             })
@@ -190,5 +195,49 @@ pub fn run<'a>(mut args: impl Iterator<Item = &'a str> + Debug) -> anyhow::Resul
 
     crate::utils::system::chmod_x(&format!("{bin_dir}/*"))?;
 
+    report_shadowed_bins(bin_dir, &installers);
+
     Ok(())
 }
+
+// Warn if an installed bin is not the first one resolved via `PATH` (e.g. an older version installed via `brew`)
+fn report_shadowed_bins(bin_dir: &str, installers: &[Box<dyn Installer>]) {
+    let Some(path) = std::env::var_os("PATH") else {
+        eprintln!("⚠️ PATH is not set, cannot check if installed bins are shadowed");
+        return;
+    };
+    let bin_dir = match Path::new(bin_dir).canonicalize() {
+        Ok(bin_dir) => bin_dir,
+        Err(e) => {
+            eprintln!("⚠️ cannot resolve bin dir {bin_dir:?} to check if installed bins are shadowed: {e}");
+            return;
+        }
+    };
+
+    if !std::env::split_paths(&path).any(|dir| dir.canonicalize().is_ok_and(|dir| dir == bin_dir)) {
+        eprintln!(
+            "⚠️ {bin_dir:?} is not in PATH, add it with `export PATH=\"{}:$PATH\"`",
+            bin_dir.display()
+        );
+        return;
+    }
+
+    for installer in installers {
+        if !bin_dir.join(installer.bin()).exists() {
+            continue;
+        }
+        let Some(first_bin_dir) = std::env::split_paths(&path)
+            .filter_map(|dir| dir.canonicalize().ok())
+            .find(|dir| dir.join(installer.bin()).is_file())
+        else {
+            continue;
+        };
+        if first_bin_dir != bin_dir {
+            eprintln!(
+                "⚠️ {} is shadowed by {:?}, move {bin_dir:?} before it in PATH",
+                installer.bin(),
+                first_bin_dir.join(installer.bin())
+            );
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use anyhow::anyhow;
 use std::fs::File;
 use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
 use std::process::Command;
 use std::process::Stdio;
 
@@ -8,13 +10,14 @@ use crate::utils::system::silent_cmd;
 
 pub enum OutputOption<'a> {
     UnpackVia(Command, &'a str),
-    PipeInto(&'a mut Command),
+    ExtractTo(&'a str),
     WriteTo(&'a str),
 }
 
 pub fn run(url: &str, output_option: OutputOption) -> anyhow::Result<()> {
     let mut curl_cmd = silent_cmd("curl");
-    curl_cmd.args(["-SL", url]);
+    // `--fail` to get an error instead of silently saving the error page (e.g. for 404s)
+    curl_cmd.args(["--fail", "-SL", url]);
 
     match output_option {
         OutputOption::UnpackVia(mut cmd, output_path) => {
@@ -29,14 +32,27 @@ pub fn run(url: &str, output_option: OutputOption) -> anyhow::Result<()> {
             let mut file = File::create(output_path)?;
             Ok(file.write_all(&output.stdout)?)
         }
-        OutputOption::PipeInto(cmd) => {
-            let curl_stdout = curl_cmd
-                .stdout(Stdio::piped())
-                .spawn()?
-                .stdout
-                .ok_or_else(|| anyhow!("missing stdout from cmd {curl_cmd:?}"))?;
+        OutputOption::ExtractTo(dest_dir) => {
+            let archive_file_name = url
+                .rsplit('/')
+                .next()
+                .ok_or_else(|| anyhow!("cannot get archive file name from url {url}"))?;
+            let archive_path = get_downloads_dir()?.join(archive_file_name);
 
-            Ok(cmd.stdin(Stdio::from(curl_stdout)).status()?.exit_ok()?)
+            curl_cmd.arg("--output");
+            curl_cmd.arg(&archive_path);
+            let extract_result = curl_cmd
+                .status()
+                .map_err(anyhow::Error::from)
+                .and_then(|status| Ok(status.exit_ok()?))
+                .and_then(|_| crate::utils::archive::extract(&archive_path, Path::new(dest_dir)));
+
+            // Removing the archive (or what's left of it) whatever happened, so failed runs don't leave junk around
+            if archive_path.exists() {
+                std::fs::remove_file(&archive_path)?;
+            }
+
+            extract_result.map(|_| ())
         }
         OutputOption::WriteTo(output_path) => {
             curl_cmd.arg("--output");
@@ -46,3 +62,9 @@ pub fn run(url: &str, output_option: OutputOption) -> anyhow::Result<()> {
         }
     }
 }
+
+pub fn get_downloads_dir() -> anyhow::Result<PathBuf> {
+    let downloads_dir = crate::utils::dirs::cache_dir()?.join("downloads");
+    std::fs::create_dir_all(&downloads_dir)?;
+    Ok(downloads_dir)
+}
@@ -1,3 +1,12 @@
+use std::path::Path;
+use std::process::Command;
+use std::process::Stdio;
+use std::time::Duration;
+use std::time::Instant;
+
+use anyhow::bail;
+use anyhow::Context;
+
 pub mod bash_language_server;
 pub mod commitlint;
 pub mod deno;
@@ -30,9 +39,18 @@ pub mod vale;
 pub mod vscode_langservers;
 pub mod yaml_language_server;
 
+const SMOKE_TEST_TIMEOUT: Duration = Duration::from_secs(10);
+
 pub trait Installer: Sync + Send {
     fn bin(&self) -> &'static str;
     fn install(&self) -> anyhow::Result<()>;
+
+    // Args to run the installed bin with to check that it actually works (e.g. `--version`).
+    // Only for tools linked into `bin_dir` that exit right away, LSPs would wait for stdin forever, and whose previous
+    // version can be restored by restoring the bin alone (e.g. not nvim, whose link target is overwritten by `make install`).
+    fn smoke_test_args(&self) -> Option<&'static [&'static str]> {
+        None
+    }
 }
 
 pub fn report_install(tool: &str, install_result: anyhow::Result<()>) -> anyhow::Result<()> {
@@ -40,3 +58,125 @@ pub fn report_install(tool: &str, install_result: anyhow::Result<()>) -> anyhow:
         .inspect(|_| println!("🎉 {tool} installed"))
         .inspect_err(|e| eprintln!("❌ error installing {tool}: {e:?}"))
 }
+
+// A copy of the previous bin is kept aside during the install and restored if the new one fails the smoke test, so a
+// tool that worked before the run is never lost and stays available while the install is running.
+pub fn install_with_smoke_test(installer: &dyn Installer, bin_dir: &str) -> anyhow::Result<()> {
+    let Some(smoke_test_args) = installer.smoke_test_args() else {
+        return installer.install();
+    };
+
+    let bin_path = Path::new(bin_dir).join(installer.bin());
+    let backup_path = Path::new(bin_dir).join(format!(".{}.bak", installer.bin()));
+    let has_backup = bin_path.symlink_metadata().is_ok();
+    if has_backup {
+        backup_bin(&bin_path, &backup_path)?;
+    }
+
+    let install_result = installer
+        .install()
+        .and_then(|_| crate::utils::system::chmod_x(&bin_path.to_string_lossy()))
+        .and_then(|_| {
+            run_smoke_test(&bin_path, smoke_test_args)
+                .with_context(|| format!("smoke test {smoke_test_args:?} failed"))
+        });
+
+    match (&install_result, has_backup) {
+        (Ok(()), true) => std::fs::remove_file(&backup_path)?,
+        (Err(_), true) => std::fs::rename(&backup_path, &bin_path)?,
+        (Err(_), false) if bin_path.symlink_metadata().is_ok() => std::fs::remove_file(&bin_path)?,
+        _ => {}
+    }
+
+    install_result
+}
+
+// Symlinks are copied as symlinks, a plain copy would follow them and the restored bin would not be a link anymore.
+fn backup_bin(bin_path: &Path, backup_path: &Path) -> anyhow::Result<()> {
+    if backup_path.symlink_metadata().is_ok() {
+        std::fs::remove_file(backup_path)?;
+    }
+    if bin_path.symlink_metadata()?.is_symlink() {
+        std::os::unix::fs::symlink(std::fs::read_link(bin_path)?, backup_path)?;
+    } else {
+        std::fs::copy(bin_path, backup_path)?;
+    }
+    Ok(())
+}
+
+fn run_smoke_test(bin_path: &Path, smoke_test_args: &[&str]) -> anyhow::Result<()> {
+    let mut smoke_test = Command::new(bin_path)
+        .args(smoke_test_args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    let start = Instant::now();
+    loop {
+        if let Some(exit_status) = smoke_test.try_wait()? {
+            return Ok(exit_status.exit_ok()?);
+        }
+        if start.elapsed() > SMOKE_TEST_TIMEOUT {
+            smoke_test.kill()?;
+            bail!("timed out after {SMOKE_TEST_TIMEOUT:?}");
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_smoke_test_works_as_expected() {
+        assert!(run_smoke_test(Path::new("true"), &["--version"]).is_ok());
+        assert!(run_smoke_test(Path::new("false"), &["--version"]).is_err());
+        assert!(run_smoke_test(Path::new("/non/existent/bin"), &["--version"]).is_err());
+    }
+
+    struct BrokenInstaller {
+        bin_dir: String,
+    }
+
+    impl Installer for BrokenInstaller {
+        fn bin(&self) -> &'static str {
+            "broken"
+        }
+
+        fn smoke_test_args(&self) -> Option<&'static [&'static str]> {
+            Some(&["--version"])
+        }
+
+        fn install(&self) -> anyhow::Result<()> {
+            Ok(std::fs::write(
+                Path::new(&self.bin_dir).join(self.bin()),
+                "#!/bin/sh\nexit 1\n",
+            )?)
+        }
+    }
+
+    #[test]
+    fn test_install_with_smoke_test_restores_the_previous_bin_if_the_smoke_test_fails() {
+        // Arrange
+        let bin_dir =
+            std::env::temp_dir().join(format!("tempura-smoke-test-{}", std::process::id()));
+        std::fs::create_dir_all(&bin_dir).unwrap();
+        std::fs::write(bin_dir.join("broken"), "previous").unwrap();
+        let installer = BrokenInstaller {
+            bin_dir: bin_dir.to_string_lossy().into(),
+        };
+
+        // Act
+        let result = install_with_smoke_test(&installer, &installer.bin_dir);
+
+        // Assert
+        let bin_content = std::fs::read_to_string(bin_dir.join("broken")).unwrap();
+        let backup_exists = bin_dir.join(".broken.bak").exists();
+        std::fs::remove_dir_all(&bin_dir).unwrap();
+        assert!(result.is_err());
+        assert_eq!("previous", bin_content);
+        assert!(!backup_exists);
+    }
+}
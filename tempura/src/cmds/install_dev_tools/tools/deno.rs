@@ -1,5 +1,3 @@
-use std::process::Command;
-
 use crate::cmds::install_dev_tools::curl_install::OutputOption;
 use crate::cmds::install_dev_tools::tools::Installer;
 
@@ -12,6 +10,10 @@ impl Installer for DenoInstaller {
         "deno"
     }
 
+    fn smoke_test_args(&self) -> Option<&'static [&'static str]> {
+        Some(&["--version"])
+    }
+
     fn install(&self) -> anyhow::Result<()> {
         // For Markdown preview with peek.nvim
         let repo = format!("{0}land/{0}", self.bin());
@@ -19,7 +21,7 @@ impl Installer for DenoInstaller {
 
         crate::cmds::install_dev_tools::curl_install::run(
             &format!("https://github.com/{repo}/releases/download/{latest_release}/{}-aarch64-apple-darwin.zip", self.bin()),
-            OutputOption::PipeInto(Command::new("tar").args(["-xz", "-C", &self.bin_dir])),
+            OutputOption::ExtractTo(&self.bin_dir),
         )
     }
 }
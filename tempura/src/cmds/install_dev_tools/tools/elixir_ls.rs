@@ -1,5 +1,3 @@
-use std::process::Command;
-
 use crate::cmds::install_dev_tools::curl_install::OutputOption;
 use crate::cmds::install_dev_tools::tools::Installer;
 use crate::utils::system::silent_cmd;
@@ -22,7 +20,7 @@ impl Installer for ElixirLsInstaller {
 
         crate::cmds::install_dev_tools::curl_install::run(
             &format!("https://github.com/{repo}/releases/download/{latest_release}/{}-{latest_release}.zip", self.bin()),
-            OutputOption::PipeInto(Command::new("tar").args(["-xz", "-C", &dev_tools_repo_dir])),
+            OutputOption::ExtractTo(&dev_tools_repo_dir),
         )?;
         crate::utils::system::chmod_x(&format!("{dev_tools_repo_dir}/*"))?;
         silent_cmd("ln")
@@ -1,5 +1,6 @@
 use crate::cmds::install_dev_tools::curl_install::OutputOption;
 use crate::cmds::install_dev_tools::tools::Installer;
+use crate::utils::system::silent_cmd;
 
 pub struct HadolintInstaller {
     pub bin_dir: String,
@@ -10,13 +11,46 @@ impl Installer for HadolintInstaller {
         "hadolint"
     }
 
+    fn smoke_test_args(&self) -> Option<&'static [&'static str]> {
+        Some(&["--version"])
+    }
+
     fn install(&self) -> anyhow::Result<()> {
-        crate::cmds::install_dev_tools::curl_install::run(
-            &format!(
-                "https://github.com/{0}/{0}/releases/latest/download/{0}-Darwin-x86_64",
-                self.bin()
-            ),
-            OutputOption::WriteTo(&format!("{}/{}", self.bin_dir, self.bin())),
-        )
+        let url = format!(
+            "https://github.com/{0}/{0}/releases/latest/download/{0}-Darwin-x86_64",
+            self.bin()
+        );
+        let downloads_dir = crate::cmds::install_dev_tools::curl_install::get_downloads_dir()?;
+        let downloaded_bin = downloads_dir.join(self.bin());
+        let downloaded_checksum = downloads_dir.join(format!("{}.sha256", self.bin()));
+
+        // Downloading outside `bin_dir` so that an unverified bin never ends up in `PATH`
+        for (url, output_path) in [
+            (format!("{url}.sha256"), &downloaded_checksum),
+            (url, &downloaded_bin),
+        ] {
+            crate::cmds::install_dev_tools::curl_install::run(
+                &url,
+                OutputOption::WriteTo(&output_path.to_string_lossy()),
+            )?;
+        }
+
+        let verification = crate::utils::system::verify_sha256(
+            &downloaded_bin,
+            &std::fs::read_to_string(&downloaded_checksum)?,
+        );
+        std::fs::remove_file(&downloaded_checksum)?;
+        if verification.is_err() {
+            std::fs::remove_file(&downloaded_bin)?;
+            return verification;
+        }
+
+        silent_cmd("mv")
+            .arg(&downloaded_bin)
+            .arg(format!("{}/{}", self.bin_dir, self.bin()))
+            .status()?
+            .exit_ok()?;
+
+        Ok(())
     }
 }
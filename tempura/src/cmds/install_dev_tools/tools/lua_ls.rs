@@ -1,5 +1,3 @@
-use std::process::Command;
-
 use crate::cmds::install_dev_tools::curl_install::OutputOption;
 use crate::cmds::install_dev_tools::tools::Installer;
 
@@ -22,7 +20,7 @@ impl Installer for LuaLanguageServer {
 
         crate::cmds::install_dev_tools::curl_install::run(
            &format!("https://github.com/{repo}/releases/download/{latest_release}/{}-{latest_release}-darwin-arm64.tar.gz", self.bin()),
-            OutputOption::PipeInto(Command::new("tar").args(["-xz", "-C", &dev_tools_repo_dir])),
+            OutputOption::ExtractTo(&dev_tools_repo_dir),
         )
     }
 }
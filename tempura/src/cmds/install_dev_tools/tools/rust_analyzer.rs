@@ -12,6 +12,10 @@ impl Installer for RustAnalyzerInstaller {
         "rust-analyzer"
     }
 
+    fn smoke_test_args(&self) -> Option<&'static [&'static str]> {
+        Some(&["--version"])
+    }
+
     fn install(&self) -> anyhow::Result<()> {
         crate::cmds::install_dev_tools::curl_install::run(
            &format!("https://github.com/rust-lang/{0}/releases/download/nightly/{0}-aarch64-apple-darwin.gz", self.bin()),
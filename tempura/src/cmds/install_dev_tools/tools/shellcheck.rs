@@ -1,5 +1,3 @@
-use std::process::Command;
-
 use crate::cmds::install_dev_tools::curl_install::OutputOption;
 use crate::cmds::install_dev_tools::tools::Installer;
 use crate::utils::system::silent_cmd;
@@ -13,13 +11,17 @@ impl Installer for ShellcheckInstaller {
         "shellcheck"
     }
 
+    fn smoke_test_args(&self) -> Option<&'static [&'static str]> {
+        Some(&["--version"])
+    }
+
     fn install(&self) -> anyhow::Result<()> {
         let repo = format!("koalaman/{}", self.bin());
         let latest_release = crate::utils::github::get_latest_release(&repo)?;
 
         crate::cmds::install_dev_tools::curl_install::run(
             &format!("https://github.com/{repo}/releases/download/{latest_release}/{}-{latest_release}.darwin.x86_64.tar.xz", self.bin()),
-            OutputOption::PipeInto(Command::new("tar").args(["-xz", "-C", "/tmp"])),
+            OutputOption::ExtractTo("/tmp"),
         )?;
 
         silent_cmd("mv")
@@ -1,5 +1,3 @@
-use std::process::Command;
-
 use crate::cmds::install_dev_tools::curl_install::OutputOption;
 use crate::cmds::install_dev_tools::tools::Installer;
 
@@ -18,7 +16,7 @@ impl Installer for TerraformLsInstaller {
 
         crate::cmds::install_dev_tools::curl_install::run(
             &format!("https://releases.hashicorp.com/{0}/{latest_release}/{0}_{latest_release}_darwin_arm64.zip", self.bin()),
-            OutputOption::PipeInto(Command::new("tar").args(["-xz", "-C", &self.bin_dir])),
+            OutputOption::ExtractTo(&self.bin_dir),
         )
     }
 }
@@ -1,5 +1,3 @@
-use std::process::Command;
-
 use crate::cmds::install_dev_tools::curl_install::OutputOption;
 use crate::cmds::install_dev_tools::tools::Installer;
 
@@ -18,7 +16,7 @@ impl Installer for TyposLspInstaller {
 
         crate::cmds::install_dev_tools::curl_install::run(
             &format!("https://github.com/{repo}/releases/download/{latest_release}/{}-{latest_release}-aarch64-apple-darwin.tar.gz", self.bin()),
-            OutputOption::PipeInto(Command::new("tar").args(["-xz", "-C", &self.bin_dir])),
+            OutputOption::ExtractTo(&self.bin_dir),
         )
     }
 }
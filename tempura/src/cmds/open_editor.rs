@@ -1,6 +1,8 @@
+use std::path::PathBuf;
 use std::str::FromStr;
 
 use anyhow::anyhow;
+use anyhow::bail;
 
 use crate::utils::system::silent_cmd;
 
@@ -18,12 +20,12 @@ impl Editor {
     }
 
     pub fn open_file_cmd(&self, file_to_open: &FileToOpen) -> String {
-        let path = file_to_open.path.as_str();
+        let path = file_to_open.path.display();
         let line_nbr = file_to_open.line_nbr;
         let column = file_to_open.column;
 
         match self {
-            Self::Helix => format!("':o {path}:{line_nbr}'"),
+            Self::Helix => format!(":o {path}:{line_nbr}"),
             Self::Nvim => format!(":e {path} | :call cursor({line_nbr}, {column})"),
         }
     }
@@ -43,7 +45,7 @@ impl FromStr for Editor {
 
 #[derive(Debug, PartialEq)]
 pub struct FileToOpen {
-    path: String,
+    path: PathBuf,
     line_nbr: i64,
     column: i64,
 }
@@ -83,36 +85,58 @@ pub fn run<'a>(mut args: impl Iterator<Item = &'a str>) -> anyhow::Result<()> {
         ));
     };
 
-    let Some(file_to_open) = args.next().map(FileToOpen::from_str).transpose()? else {
-        return Err(anyhow!(
-            "no input file specified {:?}",
-            args.collect::<Vec<_>>()
-        ));
+    let (mut file_to_open, from_clipboard) = match args.next() {
+        Some(file_to_open) => (FileToOpen::from_str(file_to_open)?, false),
+        // Defaults to the clipboard to open what `get-file-path` copied 📋
+        None => (
+            get_file_to_open_from_clipboard()
+                .ok_or_else(|| anyhow!("no input file specified and no file path in clipboard"))?,
+            true,
+        ),
     };
 
-    let editor_pane_id =
-        crate::utils::wezterm::get_current_pane_sibling_matching_titles(editor.pane_titles())
-            .map(|x| x.pane_id)?;
+    let editor_pane = crate::utils::wezterm::get_current_pane_sibling_matching_titles_and_file(
+        editor.pane_titles(),
+        &file_to_open.path,
+    )?;
+    let editor_pane_id = editor_pane.pane_id.to_string();
+    file_to_open.path = editor_pane.resolve_path(&file_to_open.path)?;
+
+    // Clipboard content is untrusted so it's used only if it's really a path to an existing file.
+    if from_clipboard && !file_to_open.path.is_file() {
+        bail!(
+            "no input file specified and clipboard path {:?} is not an existing file",
+            file_to_open.path
+        );
+    }
 
     let open_file_cmd = editor.open_file_cmd(&file_to_open);
 
-    silent_cmd("sh")
-        .args([
-            "-c",
-            &format!(
-                // `wezterm cli send-text $'\e'` sends the "ESC" to WezTerm to exit from insert mode
-                // https://github.com/wez/wezterm/discussions/3945
-                r#"
-                    wezterm cli send-text $'\e' --pane-id '{editor_pane_id}' --no-paste && \
-                        wezterm cli send-text '{open_file_cmd}' --pane-id '{editor_pane_id}' --no-paste && \
-                        printf "\r" | wezterm cli send-text --pane-id '{editor_pane_id}' --no-paste && \
-                        wezterm cli activate-pane --pane-id '{editor_pane_id}'
-                "#,
-            ),
-        ])
-        .spawn()?;
-
-    Ok(())
+    // Sending "ESC" first to exit from insert mode
+    // https://github.com/wez/wezterm/discussions/3945
+    for text in ["\x1b", &open_file_cmd, "\r"] {
+        silent_cmd("wezterm")
+            .args([
+                "cli",
+                "send-text",
+                "--pane-id",
+                &editor_pane_id,
+                "--no-paste",
+                text,
+            ])
+            .status()?
+            .exit_ok()?;
+    }
+
+    Ok(silent_cmd("wezterm")
+        .args(["cli", "activate-pane", "--pane-id", &editor_pane_id])
+        .status()?
+        .exit_ok()?)
+}
+
+fn get_file_to_open_from_clipboard() -> Option<FileToOpen> {
+    let clipboard_content = crate::utils::clipboard::read_system_clipboard().ok()?;
+    FileToOpen::from_str(clipboard_content.trim()).ok()
 }
 
 #[cfg(test)]
@@ -15,6 +15,7 @@ fn main() -> anyhow::Result<()> {
         "open-editor" => cmds::open_editor::run(cmd_args.into_iter()),
         "install-dev-tools" => cmds::install_dev_tools::run(cmd_args.into_iter()),
         "catl" => cmds::catl::run(cmd_args.into_iter()),
+        "clip-history" => cmds::clip_history::run(cmd_args.into_iter()),
         unknown_cmd => Err(anyhow!("unknown cmd '{unknown_cmd}' in args {args:?}")),
     }
 }
@@ -1,3 +1,8 @@
+pub mod archive;
+pub mod clipboard;
+pub mod clipboard_history;
+pub mod dirs;
+pub mod git;
 pub mod github;
 pub mod hx;
 pub mod system;
@@ -0,0 +1,208 @@
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::anyhow;
+use anyhow::bail;
+
+use crate::utils::system::silent_cmd;
+
+#[derive(Debug, PartialEq)]
+pub enum ArchiveFormat {
+    // `tar` detects the compression by itself so there is no need to distinguish `.tar.gz`, `.tar.xz` & co.
+    Tar,
+    Zip,
+}
+
+impl ArchiveFormat {
+    pub fn from_file_name(file_name: &str) -> anyhow::Result<Self> {
+        if file_name.ends_with(".zip") {
+            return Ok(Self::Zip);
+        }
+        if [".tar", ".tar.gz", ".tgz", ".tar.xz", ".tar.bz2"]
+            .iter()
+            .any(|ext| file_name.ends_with(ext))
+        {
+            return Ok(Self::Tar);
+        }
+        bail!("unsupported archive format for file {file_name}")
+    }
+}
+
+// Entries are listed and validated before the extraction to prevent archives from writing outside `dest_dir`.
+// Links are rejected altogether because a link entry can point anywhere and later entries can be written through it.
+pub fn extract(archive: &Path, dest_dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let archive_file_name = archive
+        .file_name()
+        .and_then(|f| f.to_str())
+        .ok_or_else(|| anyhow!("cannot get file name from archive path {archive:?}"))?;
+    let archive_format = ArchiveFormat::from_file_name(archive_file_name)?;
+
+    let entries = list_entries(archive, &archive_format)?;
+    if let Some(unsafe_entry) = entries.iter().find(|e| !is_safe_entry(e)) {
+        bail!("archive {archive:?} contains entry {unsafe_entry:?} pointing outside of the extraction dir");
+    }
+
+    if let Some(link_entry) = find_link_entry(archive, &archive_format)? {
+        bail!("archive {archive:?} contains link entry {link_entry:?}");
+    }
+
+    std::fs::create_dir_all(dest_dir)?;
+
+    match archive_format {
+        ArchiveFormat::Tar => silent_cmd("tar")
+            .arg("-xf")
+            .arg(archive)
+            .arg("-C")
+            .arg(dest_dir)
+            .status()?
+            .exit_ok()?,
+        ArchiveFormat::Zip => silent_cmd("unzip")
+            .args(["-o", "-q"])
+            .arg(archive)
+            .arg("-d")
+            .arg(dest_dir)
+            .status()?
+            .exit_ok()?,
+    }
+
+    Ok(entries.iter().map(|e| dest_dir.join(e)).collect())
+}
+
+fn list_entries(archive: &Path, archive_format: &ArchiveFormat) -> anyhow::Result<Vec<PathBuf>> {
+    let output = build_list_cmd(archive, archive_format, false).output()?;
+    output.status.exit_ok()?;
+
+    Ok(String::from_utf8(output.stdout)?
+        .lines()
+        .filter(|l| !l.is_empty())
+        .map(PathBuf::from)
+        .collect())
+}
+
+// Verbose listings start each entry with its type, `l` for symlinks and `h` for hard links (as in `ls -l`).
+// Headers and footers of `unzip -Z` never start with those.
+fn find_link_entry(
+    archive: &Path,
+    archive_format: &ArchiveFormat,
+) -> anyhow::Result<Option<String>> {
+    let output = build_list_cmd(archive, archive_format, true).output()?;
+    output.status.exit_ok()?;
+
+    Ok(String::from_utf8(output.stdout)?
+        .lines()
+        .find(|l| l.starts_with('l') || l.starts_with('h'))
+        .map(str::to_owned))
+}
+
+fn build_list_cmd(archive: &Path, archive_format: &ArchiveFormat, verbose: bool) -> Command {
+    let (program, list_arg) = match (archive_format, verbose) {
+        (ArchiveFormat::Tar, false) => ("tar", "-tf"),
+        (ArchiveFormat::Tar, true) => ("tar", "-tvf"),
+        (ArchiveFormat::Zip, false) => ("unzip", "-Z1"),
+        (ArchiveFormat::Zip, true) => ("unzip", "-Z"),
+    };
+
+    let mut cmd = Command::new(program);
+    cmd.arg(list_arg).arg(archive);
+    cmd
+}
+
+fn is_safe_entry(entry: &Path) -> bool {
+    entry
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_archive_format_from_file_name_works_as_expected() {
+        assert_eq!(
+            ArchiveFormat::Zip,
+            ArchiveFormat::from_file_name("deno-aarch64-apple-darwin.zip").unwrap()
+        );
+        assert_eq!(
+            ArchiveFormat::Tar,
+            ArchiveFormat::from_file_name("vale_3.0.0_macOS_arm64.tar.gz").unwrap()
+        );
+        assert_eq!(
+            ArchiveFormat::Tar,
+            ArchiveFormat::from_file_name("shellcheck-v0.9.0.darwin.x86_64.tar.xz").unwrap()
+        );
+        assert!(ArchiveFormat::from_file_name("rust-analyzer-aarch64-apple-darwin.gz").is_err());
+    }
+
+    #[test]
+    fn test_is_safe_entry_works_as_expected() {
+        assert!(is_safe_entry(Path::new("bin/foo")));
+        assert!(is_safe_entry(Path::new("./foo")));
+        assert!(!is_safe_entry(Path::new("/etc/passwd")));
+        assert!(!is_safe_entry(Path::new("foo/../../bar")));
+    }
+
+    #[test]
+    fn test_extract_works_as_expected_with_a_tar_gz_archive() {
+        // Arrange
+        let tmp_dir = std::env::temp_dir().join(format!("tempura-archive-{}", std::process::id()));
+        let src_dir = tmp_dir.join("src");
+        std::fs::create_dir_all(src_dir.join("bin")).unwrap();
+        std::fs::write(src_dir.join("bin/foo"), "foo").unwrap();
+        let archive = tmp_dir.join("foo.tar.gz");
+        Command::new("tar")
+            .arg("-czf")
+            .arg(&archive)
+            .arg("-C")
+            .arg(&src_dir)
+            .arg("bin")
+            .status()
+            .unwrap()
+            .exit_ok()
+            .unwrap();
+        let dest_dir = tmp_dir.join("dest");
+
+        // Act
+        let result = extract(&archive, &dest_dir);
+
+        // Assert
+        let extracted_foo = dest_dir.join("bin/foo");
+        assert!(result.unwrap().contains(&extracted_foo));
+        assert_eq!("foo", std::fs::read_to_string(extracted_foo).unwrap());
+
+        std::fs::remove_dir_all(tmp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_extract_returns_an_error_with_a_symlink_entry() {
+        // Arrange
+        let tmp_dir =
+            std::env::temp_dir().join(format!("tempura-archive-link-{}", std::process::id()));
+        let src_dir = tmp_dir.join("src");
+        std::fs::create_dir_all(&src_dir).unwrap();
+        std::os::unix::fs::symlink("/etc", src_dir.join("etc")).unwrap();
+        let archive = tmp_dir.join("foo.tar");
+        Command::new("tar")
+            .arg("-cf")
+            .arg(&archive)
+            .arg("-C")
+            .arg(&src_dir)
+            .arg("etc")
+            .status()
+            .unwrap()
+            .exit_ok()
+            .unwrap();
+        let dest_dir = tmp_dir.join("dest");
+
+        // Act
+        let result = extract(&archive, &dest_dir);
+
+        // Assert
+        let dest_dir_exists = dest_dir.exists();
+        std::fs::remove_dir_all(tmp_dir).unwrap();
+        assert!(result.is_err());
+        assert!(!dest_dir_exists);
+    }
+}
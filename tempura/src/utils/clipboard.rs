@@ -0,0 +1,211 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::process::Command;
+use std::process::Stdio;
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use anyhow::bail;
+
+use crate::utils::system::silent_cmd;
+
+const BACKEND_ENV_VAR: &str = "TEMPURA_CLIPBOARD_BACKEND";
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+#[derive(Debug, PartialEq)]
+pub enum ClipboardBackend {
+    Pbcopy,
+    WlCopy,
+    Xclip,
+    // Escape sequence understood by the terminal itself, the only option that works through SSH sessions.
+    Osc52,
+}
+
+impl ClipboardBackend {
+    pub fn detect() -> anyhow::Result<Self> {
+        if let Ok(backend) = std::env::var(BACKEND_ENV_VAR) {
+            return Self::from_str(&backend);
+        }
+        if is_set("SSH_TTY") || is_set("SSH_CONNECTION") {
+            return Ok(Self::Osc52);
+        }
+        if cfg!(target_os = "macos") {
+            return Ok(Self::Pbcopy);
+        }
+        if is_set("WAYLAND_DISPLAY") {
+            return Ok(Self::WlCopy);
+        }
+        if is_set("DISPLAY") {
+            return Ok(Self::Xclip);
+        }
+        Ok(Self::Osc52)
+    }
+
+    fn copy_cmd(&self) -> Option<Command> {
+        match self {
+            Self::Pbcopy => Some(silent_cmd("pbcopy")),
+            Self::WlCopy => Some(silent_cmd("wl-copy")),
+            Self::Xclip => {
+                let mut cmd = silent_cmd("xclip");
+                cmd.args(["-selection", "clipboard"]);
+                Some(cmd)
+            }
+            Self::Osc52 => None,
+        }
+    }
+
+    // Not using `silent_cmd` because the clipboard content is read from stdout.
+    fn paste_cmd(&self) -> Option<Command> {
+        match self {
+            Self::Pbcopy => Some(Command::new("pbpaste")),
+            Self::WlCopy => {
+                let mut cmd = Command::new("wl-paste");
+                cmd.arg("--no-newline");
+                Some(cmd)
+            }
+            Self::Xclip => {
+                let mut cmd = Command::new("xclip");
+                cmd.args(["-selection", "clipboard", "-o"]);
+                Some(cmd)
+            }
+            Self::Osc52 => None,
+        }
+    }
+}
+
+impl FromStr for ClipboardBackend {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pbcopy" => Ok(Self::Pbcopy),
+            "wl-copy" => Ok(Self::WlCopy),
+            "xclip" => Ok(Self::Xclip),
+            "osc52" => Ok(Self::Osc52),
+            s => Err(anyhow!("unknown clipboard backend {s}")),
+        }
+    }
+}
+
+pub fn copy_to_system_clipboard(content: &mut &[u8]) -> anyhow::Result<()> {
+    if crate::utils::clipboard_history::is_enabled() {
+        // History is best effort, it must never prevent the actual copy
+        if let Err(e) = crate::utils::clipboard_history::record(&String::from_utf8_lossy(content)) {
+            eprintln!("⚠️ error recording clipboard history: {e:?}");
+        }
+    }
+
+    let backend = ClipboardBackend::detect()?;
+
+    let Some(mut copy_cmd) = backend.copy_cmd() else {
+        return write_osc52(content);
+    };
+
+    let mut copy_child = copy_cmd.stdin(Stdio::piped()).spawn()?;
+    std::io::copy(
+        content,
+        copy_child
+            .stdin
+            .as_mut()
+            .ok_or_else(|| anyhow!("cannot get child stdin as mut"))?,
+    )?;
+    if !copy_child.wait()?.success() {
+        bail!("error copy content to system clipboard with {backend:?}, content {content:?}");
+    }
+    Ok(())
+}
+
+pub fn read_system_clipboard() -> anyhow::Result<String> {
+    let backend = ClipboardBackend::detect()?;
+
+    let Some(mut paste_cmd) = backend.paste_cmd() else {
+        bail!("reading the system clipboard is not supported with {backend:?}");
+    };
+
+    let output = paste_cmd.output()?;
+    output.status.exit_ok()?;
+
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+fn write_osc52(content: &[u8]) -> anyhow::Result<()> {
+    let osc52 = format!("\x1b]52;c;{}\x07", base64_encode(content));
+
+    // Writing to the tty rather than stdout to avoid polluting piped outputs.
+    match OpenOptions::new().write(true).open("/dev/tty") {
+        Ok(mut tty) => Ok(tty.write_all(osc52.as_bytes())?),
+        Err(_) => Ok(std::io::stdout().write_all(osc52.as_bytes())?),
+    }
+}
+
+fn base64_encode(input: &[u8]) -> String {
+    let mut output = String::with_capacity(input.len().div_ceil(3) * 4);
+
+    for chunk in input.chunks(3) {
+        let bytes = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or_default(),
+            chunk.get(2).copied().unwrap_or_default(),
+        ];
+        let sextets = [
+            bytes[0] >> 2,
+            (bytes[0] & 0b11) << 4 | bytes[1] >> 4,
+            (bytes[1] & 0b1111) << 2 | bytes[2] >> 6,
+            bytes[2] & 0b11_1111,
+        ];
+        for (idx, sextet) in sextets.iter().enumerate() {
+            if idx <= chunk.len() {
+                output.push(BASE64_ALPHABET[*sextet as usize] as char);
+            } else {
+                output.push('=');
+            }
+        }
+    }
+
+    output
+}
+
+fn is_set(env_var: &str) -> bool {
+    std::env::var(env_var).is_ok_and(|v| !v.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clipboard_backend_detect_works_as_expected_preferring_osc52_in_ssh_sessions() {
+        temp_env::with_vars(
+            [
+                (BACKEND_ENV_VAR, None),
+                ("SSH_TTY", Some("/dev/pts/1")),
+                ("WAYLAND_DISPLAY", Some("wayland-0")),
+            ],
+            || assert_eq!(ClipboardBackend::Osc52, ClipboardBackend::detect().unwrap()),
+        )
+    }
+
+    #[test]
+    fn test_clipboard_backend_detect_works_as_expected_with_an_explicit_backend() {
+        temp_env::with_vars(
+            [
+                (BACKEND_ENV_VAR, Some("xclip")),
+                ("SSH_TTY", Some("/dev/pts/1")),
+            ],
+            || assert_eq!(ClipboardBackend::Xclip, ClipboardBackend::detect().unwrap()),
+        )
+    }
+
+    #[test]
+    fn test_base64_encode_works_as_expected() {
+        assert_eq!("", base64_encode(b""));
+        assert_eq!("Zg==", base64_encode(b"f"));
+        assert_eq!("Zm8=", base64_encode(b"fo"));
+        assert_eq!("Zm9v", base64_encode(b"foo"));
+        assert_eq!(
+            "aHR0cHM6Ly9naXRodWIuY29tL2Z1c2lsbGljb2RlL2RvdGZpbGVz",
+            base64_encode(b"https://github.com/fusillicode/dotfiles")
+        );
+    }
+}
@@ -0,0 +1,99 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+
+const ENABLED_ENV_VAR: &str = "TEMPURA_CLIPBOARD_HISTORY";
+const MAX_ENTRIES: usize = 20;
+
+pub fn is_enabled() -> bool {
+    std::env::var(ENABLED_ENV_VAR).is_ok_and(|v| v == "1" || v == "true")
+}
+
+// Entries are stored one JSON string per line, most recent last, so multi-line content doesn't break the format.
+pub fn load() -> anyhow::Result<Vec<String>> {
+    let history_file = get_history_file_path()?;
+
+    if !history_file.exists() {
+        return Ok(vec![]);
+    }
+
+    std::fs::read_to_string(history_file)?
+        .lines()
+        .map(|l| Ok(serde_json::from_str(l)?))
+        .collect()
+}
+
+// Written via a temp file + rename so concurrent copies never leave a truncated history behind.
+// The file is readable only by the owner because it often contains passwords and tokens.
+pub fn record(content: &str) -> anyhow::Result<()> {
+    let history = push_entry(load()?, content);
+
+    let history_file = get_history_file_path()?;
+    let history_dir = history_file
+        .parent()
+        .ok_or_else(|| anyhow!("cannot get parent dir from path {history_file:?}"))?;
+    std::fs::create_dir_all(history_dir)?;
+
+    let mut serialized_history = String::new();
+    for entry in history {
+        serialized_history.push_str(&serde_json::to_string(&entry)?);
+        serialized_history.push('\n');
+    }
+
+    let tmp_history_file =
+        history_dir.join(format!(".clipboard_history.{}.tmp", std::process::id()));
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(&tmp_history_file)?
+        .write_all(serialized_history.as_bytes())?;
+
+    std::fs::rename(&tmp_history_file, &history_file).inspect_err(|_| {
+        let _ = std::fs::remove_file(&tmp_history_file);
+    })?;
+
+    Ok(())
+}
+
+fn push_entry(mut history: Vec<String>, content: &str) -> Vec<String> {
+    history.retain(|entry| entry != content);
+    history.push(content.into());
+
+    let overflow = history.len().saturating_sub(MAX_ENTRIES);
+    history.drain(..overflow);
+
+    history
+}
+
+fn get_history_file_path() -> anyhow::Result<PathBuf> {
+    Ok(crate::utils::dirs::data_dir()?.join("clipboard_history"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_entry_works_as_expected_moving_duplicates_to_the_end_and_capping_the_history() {
+        // Arrange
+        let history: Vec<String> = (0..MAX_ENTRIES).map(|i| i.to_string()).collect();
+
+        // Act
+        let result = push_entry(history, "3");
+        let result = push_entry(result, "foo");
+
+        // Assert
+        let mut expected: Vec<String> = (1..MAX_ENTRIES)
+            .filter(|i| *i != 3)
+            .map(|i| i.to_string())
+            .collect();
+        expected.push("3".into());
+        expected.push("foo".into());
+        assert_eq!(expected, result);
+    }
+}
@@ -0,0 +1,154 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::bail;
+
+const APP_NAME: &str = "tempura";
+
+pub fn home_dir() -> anyhow::Result<PathBuf> {
+    Ok(PathBuf::from(std::env::var("HOME")?))
+}
+
+// Shells expand `~` but paths coming from the clipboard or from other programs reach us verbatim.
+pub fn expand_home_dir(path: &Path) -> anyhow::Result<PathBuf> {
+    match path.strip_prefix("~") {
+        Ok(path_in_home) => Ok(home_dir()?.join(path_in_home)),
+        Err(_) => Ok(path.to_path_buf()),
+    }
+}
+
+#[allow(dead_code)]
+pub fn config_dir() -> anyhow::Result<PathBuf> {
+    get_base_dir("XDG_CONFIG_HOME", ".config", "Library/Application Support")
+}
+
+pub fn cache_dir() -> anyhow::Result<PathBuf> {
+    get_base_dir("XDG_CACHE_HOME", ".cache", "Library/Caches")
+}
+
+pub fn data_dir() -> anyhow::Result<PathBuf> {
+    get_base_dir(
+        "XDG_DATA_HOME",
+        ".local/share",
+        "Library/Application Support",
+    )
+}
+
+#[allow(dead_code)]
+pub fn state_dir() -> anyhow::Result<PathBuf> {
+    get_base_dir(
+        "XDG_STATE_HOME",
+        ".local/state",
+        "Library/Application Support",
+    )
+}
+
+fn get_base_dir(
+    xdg_env_var: &str,
+    xdg_default: &str,
+    macos_default: &str,
+) -> anyhow::Result<PathBuf> {
+    get_platform_base_dir(
+        xdg_env_var,
+        xdg_default,
+        macos_default,
+        cfg!(target_os = "macos"),
+    )
+}
+
+// XDG env vars win everywhere, otherwise fallback to the platform conventions.
+// Relative XDG paths are invalid per spec and therefore ignored.
+fn get_platform_base_dir(
+    xdg_env_var: &str,
+    xdg_default: &str,
+    macos_default: &str,
+    is_macos: bool,
+) -> anyhow::Result<PathBuf> {
+    let base_dir = match std::env::var(xdg_env_var).map(PathBuf::from) {
+        Ok(xdg_dir) if xdg_dir.is_absolute() => xdg_dir,
+        _ if is_macos => home_dir()?.join(macos_default),
+        _ => home_dir()?.join(xdg_default),
+    };
+
+    if !base_dir.is_absolute() {
+        bail!("base dir {base_dir:?} is not absolute");
+    }
+
+    Ok(base_dir.join(APP_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_home_dir_works_as_expected() {
+        temp_env::with_var("HOME", Some("/Users/Foo"), || {
+            assert_eq!(
+                PathBuf::from("/Users/Foo/dev/bar.rs"),
+                expand_home_dir(Path::new("~/dev/bar.rs")).unwrap()
+            );
+            assert_eq!(
+                PathBuf::from("dev/~/bar.rs"),
+                expand_home_dir(Path::new("dev/~/bar.rs")).unwrap()
+            );
+            assert_eq!(
+                PathBuf::from("~foo/bar.rs"),
+                expand_home_dir(Path::new("~foo/bar.rs")).unwrap()
+            );
+        })
+    }
+
+    #[test]
+    fn test_data_dir_works_as_expected_with_xdg_env_var() {
+        temp_env::with_vars(
+            [
+                ("HOME", Some("/Users/Foo")),
+                ("XDG_DATA_HOME", Some("/Users/Foo/data")),
+            ],
+            || {
+                assert_eq!(
+                    PathBuf::from("/Users/Foo/data/tempura"),
+                    data_dir().unwrap()
+                )
+            },
+        )
+    }
+
+    #[test]
+    fn test_get_platform_base_dir_works_as_expected_with_the_state_dir_macos_fallback() {
+        temp_env::with_vars(
+            [("HOME", Some("/Users/Foo")), ("XDG_STATE_HOME", None)],
+            || {
+                assert_eq!(
+                    PathBuf::from("/Users/Foo/Library/Application Support/tempura"),
+                    get_platform_base_dir(
+                        "XDG_STATE_HOME",
+                        ".local/state",
+                        "Library/Application Support",
+                        true
+                    )
+                    .unwrap()
+                )
+            },
+        )
+    }
+
+    #[test]
+    fn test_cache_dir_works_as_expected_ignoring_relative_xdg_env_var() {
+        temp_env::with_vars(
+            [
+                ("HOME", Some("/Users/Foo")),
+                ("XDG_CACHE_HOME", Some("relative/cache")),
+            ],
+            || {
+                let expected = if cfg!(target_os = "macos") {
+                    PathBuf::from("/Users/Foo/Library/Caches/tempura")
+                } else {
+                    PathBuf::from("/Users/Foo/.cache/tempura")
+                };
+                assert_eq!(expected, cache_dir().unwrap())
+            },
+        )
+    }
+}
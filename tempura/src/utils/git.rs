@@ -0,0 +1,58 @@
+use std::process::Command;
+
+use anyhow::bail;
+
+#[derive(Debug, PartialEq)]
+pub enum HeadState {
+    Branch(String),
+    Detached(String),
+    Unborn,
+}
+
+impl HeadState {
+    // The ref to use in GitHub `tree` links: a detached HEAD is linked via its commit.
+    pub fn tree_ref(&self) -> anyhow::Result<&str> {
+        match self {
+            Self::Branch(branch) => Ok(branch),
+            Self::Detached(commit) => Ok(commit),
+            Self::Unborn => bail!("HEAD has no commits yet, nothing to link"),
+        }
+    }
+}
+
+pub fn get_head_state(git_repo_root: &str) -> anyhow::Result<HeadState> {
+    let head_commit = Command::new("git")
+        .args(["-C", git_repo_root, "rev-parse", "--verify", "-q", "HEAD"])
+        .output()?;
+    if !head_commit.status.success() {
+        return Ok(HeadState::Unborn);
+    }
+
+    let symbolic_ref = Command::new("git")
+        .args(["-C", git_repo_root, "symbolic-ref", "--short", "-q", "HEAD"])
+        .output()?;
+    if symbolic_ref.status.success() {
+        return Ok(HeadState::Branch(
+            String::from_utf8(symbolic_ref.stdout)?.trim().to_owned(),
+        ));
+    }
+
+    Ok(HeadState::Detached(
+        String::from_utf8(head_commit.stdout)?.trim().to_owned(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_head_state_tree_ref_works_as_expected() {
+        assert_eq!("main", HeadState::Branch("main".into()).tree_ref().unwrap());
+        assert_eq!(
+            "4f1b2c3",
+            HeadState::Detached("4f1b2c3".into()).tree_ref().unwrap()
+        );
+        assert!(HeadState::Unborn.tree_ref().is_err());
+    }
+}
@@ -1,32 +1,333 @@
 use std::process::Command;
 
+use anyhow::anyhow;
+use anyhow::bail;
+
 use crate::utils::system::silent_cmd;
 
+const REQUIRED_SCOPES: [&str; 3] = ["repo", "read:org", "workflow"];
+
 pub fn log_into_github() -> anyhow::Result<()> {
-    if silent_cmd("gh")
-        .args(["auth", "status"])
-        .status()?
-        .success()
+    let gh_auth_status = match get_gh_auth_status()? {
+        Some(gh_auth_status) => gh_auth_status,
+        None => {
+            silent_cmd("sh")
+                .args(["-c", "gh auth login"])
+                .status()?
+                .exit_ok()?;
+            get_gh_auth_status()?.unwrap_or_default()
+        }
+    };
+
+    // Not failing here because most of the calls only read public data and tokens from `GH_TOKEN` or fine-grained PATs
+    // cannot be introspected or refreshed anyway.
+    ensure_required_scopes(&gh_auth_status, &REQUIRED_SCOPES);
+
+    Ok(())
+}
+
+pub fn get_latest_release(repo: &str) -> anyhow::Result<String> {
+    let gh_api_output = call_gh_api(&format!("repos/{repo}/releases/latest"))?;
+
+    let release: serde_json::Value =
+        serde_json::from_str(parse_gh_api_output(&gh_api_output).body)?;
+    release["tag_name"]
+        .as_str()
+        .map(str::to_owned)
+        .ok_or_else(|| anyhow!("no tag_name in latest release of repo {repo}"))
+}
+
+fn ensure_required_scopes(gh_auth_status: &str, required_scopes: &[&str]) {
+    let gh_auth_status = match get_missing_scopes(gh_auth_status, required_scopes) {
+        Some(missing_scopes) if !missing_scopes.is_empty() => {
+            if let Err(e) = refresh_scopes(&missing_scopes) {
+                eprintln!("⚠️ cannot refresh gh token scopes {missing_scopes:?}: {e}");
+            }
+            get_gh_auth_status().ok().flatten().unwrap_or_default()
+        }
+        _ => gh_auth_status.to_owned(),
+    };
+
+    if let Err(e) = validate_scopes(&gh_auth_status, required_scopes) {
+        eprintln!("⚠️ {e}");
+    }
+}
+
+// Pre-flight check to report missing scopes and SSO authorizations before they fail the actual `gh` calls
+pub fn validate_scopes(gh_auth_status: &str, required_scopes: &[&str]) -> anyhow::Result<()> {
+    let missing_scopes = get_missing_scopes(gh_auth_status, required_scopes).ok_or_else(|| {
+        anyhow!(
+            "cannot determine gh token scopes, make sure the token has scopes {required_scopes:?} with `gh auth refresh --scopes {}`",
+            required_scopes.join(",")
+        )
+    })?;
+
+    if !missing_scopes.is_empty() {
+        bail!(
+            "gh token is missing scopes {missing_scopes:?}, add them with `gh auth refresh --scopes {}`",
+            missing_scopes.join(",")
+        );
+    }
+
+    // Repos of organizations the token is not SSO authorized for are silently omitted and reported only via header
+    let gh_api_output = call_gh_api("user/repos")?;
+    if let Some(sso_error) = parse_gh_api_output(&gh_api_output)
+        .x_github_sso
+        .and_then(build_sso_error)
     {
-        return Ok(());
+        return Err(sso_error);
     }
 
-    Ok(silent_cmd("sh")
-        .args(["-c", "gh auth login"])
+    Ok(())
+}
+
+// Not using `silent_cmd` because `gh auth refresh` is interactive: it prints a one-time code and waits for input.
+fn refresh_scopes(scopes: &[&str]) -> anyhow::Result<()> {
+    Ok(Command::new("gh")
+        .args(["auth", "refresh", "--scopes", &scopes.join(",")])
         .status()?
         .exit_ok()?)
 }
 
-pub fn get_latest_release(repo: &str) -> anyhow::Result<String> {
-    let output = Command::new("gh")
-        .args([
-            "api",
-            &format!("repos/{repo}/releases/latest"),
-            "--jq=.tag_name",
-        ])
-        .output()?;
+// `None` if not logged in
+fn get_gh_auth_status() -> anyhow::Result<Option<String>> {
+    let output = Command::new("gh").args(["auth", "status"]).output()?;
+    if !output.status.success() {
+        return Ok(None);
+    }
+
+    // Depending on the `gh` version the status is reported either on stdout or on stderr 🥲
+    Ok(Some(
+        [
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr),
+        ]
+        .concat(),
+    ))
+}
+
+fn get_missing_scopes<'a>(
+    gh_auth_status: &str,
+    required_scopes: &[&'a str],
+) -> Option<Vec<&'a str>> {
+    let token_scopes = get_token_scopes_from_gh_auth_status_output(gh_auth_status)?;
+
+    Some(
+        required_scopes
+            .iter()
+            .filter(|scope| !token_scopes.contains(scope))
+            .copied()
+            .collect(),
+    )
+}
+
+struct GhApiResponse<'a> {
+    x_github_sso: Option<&'a str>,
+    body: &'a str,
+}
+
+fn call_gh_api(endpoint: &str) -> anyhow::Result<String> {
+    // `-i` to get the headers, where GitHub reports SSO problems
+    let output = Command::new("gh").args(["api", "-i", endpoint]).output()?;
+    let gh_api_output = String::from_utf8(output.stdout)?;
+
+    if !output.status.success() {
+        return Err(build_gh_api_error(
+            std::str::from_utf8(&output.stderr)?,
+            parse_gh_api_output(&gh_api_output).x_github_sso,
+        ));
+    }
+
+    Ok(gh_api_output)
+}
+
+fn parse_gh_api_output(gh_api_output: &str) -> GhApiResponse<'_> {
+    let (headers, body) = gh_api_output
+        .split_once("\r\n\r\n")
+        .or_else(|| gh_api_output.split_once("\n\n"))
+        .unwrap_or(("", gh_api_output));
+
+    let x_github_sso = headers.lines().find_map(|l| {
+        let (name, value) = l.split_once(':')?;
+        name.trim()
+            .eq_ignore_ascii_case("x-github-sso")
+            .then_some(value.trim())
+    });
+
+    GhApiResponse { x_github_sso, body }
+}
+
+fn build_gh_api_error(gh_api_stderr: &str, x_github_sso: Option<&str>) -> anyhow::Error {
+    let gh_api_stderr = gh_api_stderr.trim();
+
+    x_github_sso
+        .and_then(build_sso_error)
+        .map(|sso_error| sso_error.context(gh_api_stderr.to_owned()))
+        .unwrap_or_else(|| anyhow!("gh api error: {gh_api_stderr}"))
+}
+
+// The `X-GitHub-SSO` header is either `required; url=<authorization url>` or `partial-results; organizations=<ids>`
+fn build_sso_error(x_github_sso: &str) -> Option<anyhow::Error> {
+    let (kind, params) = x_github_sso.split_once(';')?;
+    let (param_name, param_value) = params.trim().split_once('=')?;
+
+    match (kind.trim(), param_name) {
+        ("required", "url") => Some(anyhow!(
+            "gh token is not authorized for the organization SAML SSO, authorize it at {param_value}"
+        )),
+        ("partial-results", "organizations") => {
+            let organizations: Vec<String> = param_value
+                .split(',')
+                .map(|org_id| get_organization_login(org_id.trim()))
+                .collect();
+            Some(anyhow!(
+                "gh token is not authorized for the SAML SSO of organizations {organizations:?}, authorize it with `gh auth refresh` or in https://github.com/settings/tokens"
+            ))
+        }
+        _ => None,
+    }
+}
+
+// Best effort, fallbacks to the org id if the login cannot be retrieved
+fn get_organization_login(org_id: &str) -> String {
+    Command::new("gh")
+        .args(["api", &format!("organizations/{org_id}"), "--jq=.login"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|login| login.trim().to_owned())
+        .filter(|login| !login.is_empty())
+        .unwrap_or_else(|| org_id.to_owned())
+}
+
+// `None` if there is no scopes line, e.g. for tokens coming from `GH_TOKEN` or fine-grained PATs
+fn get_token_scopes_from_gh_auth_status_output(gh_auth_status_output: &str) -> Option<Vec<&str>> {
+    let (_, token_scopes) = gh_auth_status_output
+        .lines()
+        .find_map(|l| l.split_once("Token scopes:"))?;
+
+    Some(
+        token_scopes
+            .split(',')
+            .map(|scope| scope.trim().trim_matches('\''))
+            .filter(|scope| !scope.is_empty())
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_token_scopes_from_gh_auth_status_output_works_as_expected_with_quoted_scopes() {
+        // Arrange
+        let input = r#"
+            github.com
+              ✓ Logged in to github.com account fusillicode (keyring)
+              - Active account: true
+              - Git operations protocol: ssh
+              - Token: gho_************************************
+              - Token scopes: 'gist', 'read:org', 'repo'
+        "#;
+
+        // Act
+        let result = get_token_scopes_from_gh_auth_status_output(input).unwrap();
+
+        // Assert
+        let expected = vec!["gist", "read:org", "repo"];
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_get_token_scopes_from_gh_auth_status_output_works_as_expected_with_unquoted_scopes() {
+        // Arrange
+        let input = r#"
+            github.com
+              ✓ Logged in to github.com as fusillicode (keyring)
+              ✓ Git operations for github.com configured to use ssh protocol.
+              ✓ Token: gho_************************************
+              ✓ Token scopes: gist, read:org, repo, workflow
+        "#;
 
-    output.status.exit_ok()?;
+        // Act
+        let result = get_token_scopes_from_gh_auth_status_output(input).unwrap();
 
-    Ok(std::str::from_utf8(&output.stdout)?.trim().into())
+        // Assert
+        let expected = vec!["gist", "read:org", "repo", "workflow"];
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_get_token_scopes_from_gh_auth_status_output_returns_none_without_a_token_scopes_line() {
+        // Arrange
+        let input = r#"
+            github.com
+              ✓ Logged in to github.com account fusillicode (GH_TOKEN)
+              - Active account: true
+              - Git operations protocol: https
+              - Token: github_pat_************************************
+        "#;
+
+        // Act
+        let result = get_token_scopes_from_gh_auth_status_output(input);
+
+        // Assert
+        assert_eq!(None, result);
+    }
+
+    #[test]
+    fn test_parse_gh_api_output_works_as_expected_with_the_x_github_sso_header() {
+        // Arrange
+        let input = "HTTP/2.0 403 Forbidden\r\nContent-Type: application/json\r\nX-Github-Sso: required; url=https://github.com/orgs/foo/sso?authorization_request=bar\r\n\r\n{\"message\":\"Resource protected by organization SAML enforcement.\"}";
+
+        // Act
+        let result = parse_gh_api_output(input);
+
+        // Assert
+        assert_eq!(
+            Some("required; url=https://github.com/orgs/foo/sso?authorization_request=bar"),
+            result.x_github_sso
+        );
+        assert_eq!(
+            "{\"message\":\"Resource protected by organization SAML enforcement.\"}",
+            result.body
+        );
+    }
+
+    #[test]
+    fn test_parse_gh_api_output_works_as_expected_without_the_x_github_sso_header() {
+        // Arrange
+        let input = "HTTP/2.0 200 OK\nContent-Type: application/json\n\n{\"tag_name\":\"v1.0.0\"}";
+
+        // Act
+        let result = parse_gh_api_output(input);
+
+        // Assert
+        assert_eq!(None, result.x_github_sso);
+        assert_eq!("{\"tag_name\":\"v1.0.0\"}", result.body);
+    }
+
+    #[test]
+    fn test_build_sso_error_works_as_expected_with_required_sso() {
+        // Act
+        let result = build_sso_error(
+            "required; url=https://github.com/orgs/foo/sso?authorization_request=bar",
+        )
+        .unwrap()
+        .to_string();
+
+        // Assert
+        assert_eq!(
+            "gh token is not authorized for the organization SAML SSO, authorize it at https://github.com/orgs/foo/sso?authorization_request=bar",
+            result
+        );
+    }
+
+    #[test]
+    fn test_build_sso_error_returns_none_with_unknown_values() {
+        assert!(build_sso_error("foo; bar=baz").is_none());
+        assert!(build_sso_error("required").is_none());
+    }
 }
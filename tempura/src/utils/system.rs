@@ -1,9 +1,13 @@
+use std::path::Path;
 use std::process::Command;
 use std::process::Stdio;
 use std::thread::JoinHandle;
 
 use anyhow::anyhow;
 use anyhow::bail;
+use serde::de::DeserializeOwned;
+
+const STDOUT_SNIPPET_MAX_CHARS: usize = 200;
 
 pub fn join<T>(join_handle: JoinHandle<anyhow::Result<T>>) -> Result<T, anyhow::Error> {
     join_handle
@@ -11,21 +15,6 @@ pub fn join<T>(join_handle: JoinHandle<anyhow::Result<T>>) -> Result<T, anyhow::
         .map_err(|e| anyhow!("join error {e:?}"))?
 }
 
-pub fn copy_to_system_clipboard(content: &mut &[u8]) -> anyhow::Result<()> {
-    let mut pbcopy_child = silent_cmd("pbcopy").stdin(Stdio::piped()).spawn()?;
-    std::io::copy(
-        content,
-        pbcopy_child
-            .stdin
-            .as_mut()
-            .ok_or_else(|| anyhow!("cannot get child stdin as mut"))?,
-    )?;
-    if !pbcopy_child.wait()?.success() {
-        bail!("error copy content to system clipboard, content {content:?}");
-    }
-    Ok(())
-}
-
 // Yes, `dir` is a `&str` and it's not sanitized but...I'm the alpha & the omega here!
 pub fn chmod_x(dir: &str) -> anyhow::Result<()> {
     Ok(silent_cmd("sh")
@@ -34,6 +23,54 @@ pub fn chmod_x(dir: &str) -> anyhow::Result<()> {
         .exit_ok()?)
 }
 
+pub fn exec_json<T: DeserializeOwned>(cmd: &mut Command) -> anyhow::Result<T> {
+    let output = cmd.output()?;
+
+    if !output.status.success() {
+        bail!(
+            "error running cmd {cmd:?}, status {}, stderr {:?}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    serde_json::from_slice(&output.stdout).map_err(|e| {
+        anyhow!(
+            "cannot deserialize output of cmd {cmd:?}, error {e}, stdout {:?}",
+            truncate(
+                &String::from_utf8_lossy(&output.stdout),
+                STDOUT_SNIPPET_MAX_CHARS
+            )
+        )
+    })
+}
+
+pub fn compute_sha256(path: &Path) -> anyhow::Result<String> {
+    let output = Command::new("shasum")
+        .args(["-a", "256"])
+        .arg(path)
+        .output()?;
+    output.status.exit_ok()?;
+
+    let shasum_output = std::str::from_utf8(&output.stdout)?;
+    get_sha256_from_checksum_line(shasum_output)
+        .map(str::to_owned)
+        .ok_or_else(|| anyhow!("no sha256 in shasum output '{shasum_output}' for file {path:?}"))
+}
+
+// `expected` can be either the bare hash or a `<hash>  <file>` line as published alongside releases.
+pub fn verify_sha256(path: &Path, expected: &str) -> anyhow::Result<()> {
+    let expected_sha256 = get_sha256_from_checksum_line(expected)
+        .ok_or_else(|| anyhow!("no sha256 in expected checksum '{expected}' for file {path:?}"))?;
+    let actual_sha256 = compute_sha256(path)?;
+
+    if !actual_sha256.eq_ignore_ascii_case(expected_sha256) {
+        bail!("sha256 mismatch for file {path:?}, expected {expected_sha256} got {actual_sha256}");
+    }
+
+    Ok(())
+}
+
 pub fn silent_cmd(program: &str) -> Command {
     let mut cmd = Command::new(program);
     if !cfg!(debug_assertions) {
@@ -41,3 +78,72 @@ pub fn silent_cmd(program: &str) -> Command {
     }
     cmd
 }
+
+fn get_sha256_from_checksum_line(checksum_line: &str) -> Option<&str> {
+    checksum_line
+        .split_whitespace()
+        .next()
+        .filter(|sha256| sha256.len() == 64 && sha256.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+fn truncate(s: &str, max_chars: usize) -> String {
+    match s.char_indices().nth(max_chars) {
+        Some((idx, _)) => format!("{}...", &s[..idx]),
+        None => s.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_sha256_works_as_expected() {
+        // Arrange
+        let file = std::env::temp_dir().join(format!("tempura-sha256-{}", std::process::id()));
+        std::fs::write(&file, "foo").unwrap();
+        let foo_sha256 = "2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae";
+
+        // Act
+        let result_with_bare_hash = verify_sha256(&file, foo_sha256);
+        let result_with_checksum_line = verify_sha256(&file, &format!("{foo_sha256}  foo\n"));
+        let result_with_wrong_hash = verify_sha256(&file, &"0".repeat(64));
+
+        // Assert
+        std::fs::remove_file(&file).unwrap();
+        assert!(result_with_bare_hash.is_ok());
+        assert!(result_with_checksum_line.is_ok());
+        assert!(result_with_wrong_hash.is_err());
+    }
+
+    #[test]
+    fn test_exec_json_works_as_expected_with_valid_json_stdout() {
+        // Act
+        let result: Vec<i64> = exec_json(Command::new("echo").arg("[1, 2, 3]")).unwrap();
+
+        // Assert
+        assert_eq!(vec![1, 2, 3], result);
+    }
+
+    #[test]
+    fn test_exec_json_returns_an_error_with_a_truncated_stdout_snippet_if_stdout_is_not_json() {
+        // Act
+        let result = exec_json::<Vec<i64>>(
+            Command::new("sh").args(["-c", r"head -c 500 /dev/zero | tr '\0' x"]),
+        );
+
+        // Assert
+        let error = result.unwrap_err().to_string();
+        assert!(error.contains(&format!("{}...", "x".repeat(STDOUT_SNIPPET_MAX_CHARS))));
+        assert!(!error.contains(&"x".repeat(STDOUT_SNIPPET_MAX_CHARS + 1)));
+    }
+
+    #[test]
+    fn test_exec_json_returns_an_error_if_the_cmd_fails() {
+        // Act
+        let result = exec_json::<Vec<i64>>(&mut Command::new("false"));
+
+        // Assert
+        assert!(result.is_err());
+    }
+}
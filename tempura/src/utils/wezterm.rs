@@ -1,3 +1,6 @@
+use std::cmp::Reverse;
+use std::path::Component;
+use std::path::Path;
 use std::path::PathBuf;
 use std::process::Command;
 
@@ -7,15 +10,39 @@ use serde::Deserialize;
 pub fn get_current_pane_sibling_matching_titles(
     pane_titles: &[&str],
 ) -> anyhow::Result<WezTermPane> {
-    let current_pane_id: i64 = std::env::var("WEZTERM_PANE")?.parse()?;
+    select_current_pane_sibling(&get_all_panes()?, get_current_pane_id()?, pane_titles, None)
+}
+
+// Among multiple matching panes prefers the one whose cwd contains `file_path`, then the lowest id.
+// Relative `file_path`s are looked up in the cwd of each pane.
+pub fn get_current_pane_sibling_matching_titles_and_file(
+    pane_titles: &[&str],
+    file_path: &Path,
+) -> anyhow::Result<WezTermPane> {
+    select_current_pane_sibling(
+        &get_all_panes()?,
+        get_current_pane_id()?,
+        pane_titles,
+        Some(&crate::utils::dirs::expand_home_dir(file_path)?),
+    )
+}
+
+fn get_current_pane_id() -> anyhow::Result<i64> {
+    Ok(std::env::var("WEZTERM_PANE")?.parse()?)
+}
 
-    let all_panes: Vec<WezTermPane> = serde_json::from_slice(
-        &Command::new("wezterm")
-            .args(["cli", "list", "--format", "json"])
-            .output()?
-            .stdout,
-    )?;
+fn get_all_panes() -> anyhow::Result<Vec<WezTermPane>> {
+    crate::utils::system::exec_json(
+        Command::new("wezterm").args(["cli", "list", "--format", "json"]),
+    )
+}
 
+fn select_current_pane_sibling(
+    all_panes: &[WezTermPane],
+    current_pane_id: i64,
+    pane_titles: &[&str],
+    file_path: Option<&Path>,
+) -> anyhow::Result<WezTermPane> {
     let current_pane_tab_id = all_panes
         .iter()
         .find(|w| w.pane_id == current_pane_id)
@@ -26,7 +53,21 @@ pub fn get_current_pane_sibling_matching_titles(
 
     Ok(all_panes
         .iter()
-        .find(|w| w.tab_id == current_pane_tab_id && pane_titles.contains(&w.title.as_str()))
+        .filter(|w| w.tab_id == current_pane_tab_id && pane_titles.contains(&w.title.as_str()))
+        .max_by_key(|w| {
+            let cwd = w.absolute_cwd();
+            let cwd_depth = file_path
+                .filter(|file_path| {
+                    if file_path.is_relative() {
+                        cwd.join(file_path).is_file()
+                    } else {
+                        file_path.starts_with(&cwd)
+                    }
+                })
+                .map(|_| cwd.components().count())
+                .unwrap_or_default();
+            (cwd_depth, Reverse(w.pane_id))
+        })
         .ok_or({
             anyhow!("pane with title '{pane_titles:?}' not found in tab '{current_pane_tab_id}'")
         })?
@@ -57,6 +98,25 @@ pub struct WezTermPane {
     pub tty_name: String,
 }
 
+impl WezTermPane {
+    pub fn absolute_cwd(&self) -> PathBuf {
+        let mut components = self.cwd.components();
+        components.next();
+        components.next();
+
+        std::iter::once(Component::RootDir)
+            .chain(components)
+            .collect()
+    }
+
+    // Resolves `path` the way a program running in the pane would, i.e. relative to the pane cwd.
+    pub fn resolve_path(&self, path: &Path) -> anyhow::Result<PathBuf> {
+        Ok(self
+            .absolute_cwd()
+            .join(crate::utils::dirs::expand_home_dir(path)?))
+    }
+}
+
 #[derive(Debug, Deserialize, Clone)]
 #[cfg_attr(any(test), derive(fake::Dummy))]
 #[allow(dead_code)]
@@ -67,3 +127,114 @@ pub struct WezTermPaneSize {
     pub pixel_height: i64,
     pub dpi: i64,
 }
+
+#[cfg(test)]
+mod tests {
+    use fake::Fake;
+    use fake::Faker;
+
+    use super::*;
+
+    fn build_pane(
+        pane_id: i64,
+        tab_id: i64,
+        title: &str,
+        cwd: &str,
+        is_active: bool,
+    ) -> WezTermPane {
+        WezTermPane {
+            pane_id,
+            tab_id,
+            title: title.into(),
+            cwd: Path::new(cwd).into(),
+            is_active,
+            ..Faker.fake()
+        }
+    }
+
+    #[test]
+    fn test_select_current_pane_sibling_works_as_expected_preferring_the_pane_whose_cwd_contains_the_file(
+    ) {
+        // Arrange
+        let all_panes = [
+            build_pane(1, 7, "zsh", "file://hostname/Users/Foo", true),
+            build_pane(2, 7, "hx", "file://hostname/Users/Foo/dev/bar", false),
+            build_pane(3, 7, "hx", "file://hostname/Users/Foo/dev/baz", false),
+            build_pane(4, 8, "hx", "file://hostname/Users/Foo/dev/baz/src", true),
+        ];
+
+        // Act
+        let result = select_current_pane_sibling(
+            &all_panes,
+            1,
+            &["hx"],
+            Some(Path::new("/Users/Foo/dev/baz/src/main.rs")),
+        )
+        .unwrap();
+
+        // Assert
+        assert_eq!(3, result.pane_id);
+    }
+
+    #[test]
+    fn test_select_current_pane_sibling_works_as_expected_falling_back_to_the_lowest_id() {
+        // Arrange
+        let all_panes = [
+            build_pane(1, 7, "zsh", "file://hostname/Users/Foo", true),
+            build_pane(3, 7, "hx", "file://hostname/Users/Foo/dev/baz", false),
+            build_pane(2, 7, "hx", "file://hostname/Users/Foo/dev/bar", false),
+            build_pane(4, 7, "hx", "file://hostname/Users/Foo/dev/qux", false),
+        ];
+
+        // Act
+        let result = select_current_pane_sibling(
+            &all_panes,
+            1,
+            &["hx"],
+            Some(Path::new("/Users/Foo/other/main.rs")),
+        )
+        .unwrap();
+
+        // Assert
+        assert_eq!(2, result.pane_id);
+    }
+
+    #[test]
+    fn test_resolve_path_works_as_expected() {
+        // Arrange
+        let pane = build_pane(1, 7, "hx", "file://hostname/Users/Foo/dev/bar", true);
+
+        temp_env::with_var("HOME", Some("/Users/Foo"), || {
+            // Act & Assert
+            assert_eq!(
+                PathBuf::from("/Users/Foo/dev/bar/src/main.rs"),
+                pane.resolve_path(Path::new("src/main.rs")).unwrap()
+            );
+            assert_eq!(
+                PathBuf::from("/Users/Foo/dev/baz/src/main.rs"),
+                pane.resolve_path(Path::new("~/dev/baz/src/main.rs"))
+                    .unwrap()
+            );
+            assert_eq!(
+                PathBuf::from("/tmp/main.rs"),
+                pane.resolve_path(Path::new("/tmp/main.rs")).unwrap()
+            );
+        })
+    }
+
+    #[test]
+    fn test_select_current_pane_sibling_returns_an_error_if_no_pane_matches_the_titles_in_the_current_tab(
+    ) {
+        // Arrange
+        let all_panes = [
+            build_pane(1, 7, "zsh", "file://hostname/Users/Foo", true),
+            build_pane(2, 8, "hx", "file://hostname/Users/Foo/dev/bar", true),
+        ];
+
+        // Act
+        let result = select_current_pane_sibling(&all_panes, 1, &["hx"], None);
+
+        // Assert
+        assert!(result.is_err());
+    }
+}